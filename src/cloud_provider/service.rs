@@ -1,7 +1,11 @@
-use std::io::Error;
-use std::net::TcpStream;
+use std::io::{Error, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
 use std::process::id;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
+use serde_json::Value as JsonValue;
 use tera::Context as TeraContext;
 
 use crate::build_platform::Image;
@@ -23,15 +27,21 @@ pub trait Service {
     fn total_cpus(&self) -> String;
     fn total_ram_in_mib(&self) -> u32;
     fn total_instances(&self) -> u16;
-    fn is_listening(&self, ip: &str) -> bool {
-        let private_port = match self.private_port() {
-            Some(private_port) => private_port,
-            _ => return false,
-        };
+    /// What a readiness check should look like for this service. Defaults to a plain TCP
+    /// connect on `private_port`, matching the previous `is_listening` behavior. `None`
+    /// means this service has nothing to probe (no `private_port`).
+    fn probe_spec(&self) -> Option<ProbeSpec> {
+        self.private_port().map(|port| ProbeSpec::Tcp { port })
+    }
 
-        match TcpStream::connect(format!("{}:{}", ip, private_port)) {
-            Ok(_) => true,
-            Err(_) => false,
+    /// Polls `probe_spec()` against `ip` until it succeeds or `policy`'s total deadline
+    /// elapses. Each attempt runs on its own thread bounded by `policy.attempt_timeout`,
+    /// so an unreachable IP can never stall the caller past the deadline. Fails fast,
+    /// without spending any of the deadline, when there's nothing to probe.
+    fn wait_until_ready(&self, ip: &str, policy: ProbePolicy) -> Result<(), ServiceError> {
+        match self.probe_spec() {
+            Some(spec) => wait_until_ready_for(&spec, ip, policy),
+            None => Err(ServiceError::CheckFailed),
         }
     }
 
@@ -104,7 +114,7 @@ pub trait StatelessService: Service + Create + Pause + Delete {
 }
 
 pub trait StatefulService:
-    Service + Create + Pause + Delete + Backup + Clone + Upgrade + Downgrade
+    Service + Create + Pause + Delete + Backup + Clone + Upgrade + Downgrade + Migrate
 {
     fn exec_action(&self, deployment_target: &DeploymentTarget) -> Result<(), ServiceError> {
         match self.action() {
@@ -114,6 +124,75 @@ pub trait StatefulService:
             crate::cloud_provider::service::Action::Nothing => Ok(()),
         }
     }
+
+    /// Inserts the operator CRD's topology, pooler endpoint and backup hook so
+    /// `on_create` can render and apply it for `DeploymentStrategy::Operator` databases.
+    fn operator_tera_context(
+        &self,
+        database_type: &DatabaseType,
+        topology: &DatabaseTopology,
+        context: &mut TeraContext,
+    ) {
+        let strategy = database_type.deployment_strategy();
+
+        context.insert(
+            "deployment_strategy",
+            match strategy {
+                DeploymentStrategy::Raw => "raw",
+                DeploymentStrategy::Operator => "operator",
+            },
+        );
+
+        if let DeploymentStrategy::Operator = strategy {
+            context.insert("operator_name", database_type.operator_name());
+        }
+
+        context.insert("topology_instances", &topology.instances);
+        context.insert("topology_storage_class", &topology.storage_class);
+        context.insert(
+            "topology_synchronous_replicas",
+            &topology.synchronous_replicas,
+        );
+        context.insert("pooler_enabled", &topology.pooler.is_some());
+
+        if let Some(pooler) = &topology.pooler {
+            context.insert("pooler_image", &pooler.image);
+            context.insert("pooler_port", &pooler.port);
+            context.insert("pooler_max_connections", &pooler.max_connections);
+        }
+
+        context.insert("backup_hook", &format!("{}-backup", self.id()));
+    }
+
+    /// Waits for an operator-managed cluster by probing its pooler endpoint over TCP,
+    /// reusing the readiness-probe subsystem. When `topology` has no pooler, falls back
+    /// to the service's own `probe_spec()` via `wait_until_ready`.
+    fn wait_for_cluster_ready(
+        &self,
+        ip: &str,
+        topology: &DatabaseTopology,
+        policy: ProbePolicy,
+    ) -> Result<(), ServiceError> {
+        match &topology.pooler {
+            Some(pooler) => wait_until_ready_for(&ProbeSpec::Tcp { port: pooler.port }, ip, policy),
+            None => self.wait_until_ready(ip, policy),
+        }
+    }
+
+    /// The `Service::default_tera_context` integration point for stateful services:
+    /// starts from the base context and layers in the current/target/pending schema
+    /// version keys from `Migrate::migration_tera_context`. No incidental trait bound
+    /// beyond `StatefulService` itself, so every stateful service gets this for free.
+    fn stateful_tera_context(
+        &self,
+        kubernetes: &dyn Kubernetes,
+        environment: &Environment,
+        applied_migrations: &[MigrationRecord],
+    ) -> TeraContext {
+        let mut context = Service::default_tera_context(self, kubernetes, environment);
+        self.migration_tera_context(applied_migrations, &mut context);
+        context
+    }
 }
 
 pub trait Application: StatelessService {
@@ -174,6 +253,678 @@ pub trait Downgrade {
     fn on_downgrade_error(&self, target: &DeploymentTarget) -> Result<(), ServiceError>;
 }
 
+pub trait Migrate {
+    fn on_migrate(&self, target: &DeploymentTarget) -> Result<(), ServiceError>;
+    fn on_migrate_check(&self) -> Result<(), ServiceError>;
+    fn on_migrate_error(&self, target: &DeploymentTarget) -> Result<(), ServiceError>;
+    fn on_rollback(&self, target: &DeploymentTarget) -> Result<(), ServiceError>;
+    fn on_rollback_check(&self) -> Result<(), ServiceError>;
+    fn on_rollback_error(&self, target: &DeploymentTarget) -> Result<(), ServiceError>;
+
+    fn migrations(&self) -> &[Migration];
+
+    fn migration_tera_context(&self, applied: &[MigrationRecord], context: &mut TeraContext) {
+        let current_version = applied.iter().map(|r| r.version).max().unwrap_or(0);
+        let target_version = self
+            .migrations()
+            .iter()
+            .map(|m| m.version)
+            .max()
+            .unwrap_or(current_version);
+        let pending_versions: Vec<i64> = self
+            .migrations()
+            .iter()
+            .map(|m| m.version)
+            .filter(|version| *version > current_version)
+            .collect();
+
+        context.insert("current_schema_version", &current_version);
+        context.insert("target_schema_version", &target_version);
+        context.insert("pending_schema_versions", &pending_versions);
+    }
+}
+
+/// A single, checksummed schema change applied in its own transaction.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Migration {
+    pub version: i64,
+    pub checksum: String,
+    pub up: String,
+    pub down: String,
+}
+
+/// A row of the `schema_migrations` bookkeeping table.
+#[derive(Clone, Eq, PartialEq)]
+pub struct MigrationRecord {
+    pub version: i64,
+    pub checksum: String,
+    pub applied_at: String,
+}
+
+/// Backend-specific bookkeeping and execution, implemented once per `DatabaseType`.
+pub trait MigrationExecutor {
+    fn ensure_schema_migrations_table(&self) -> Result<(), ServiceError>;
+    fn applied_migrations(&self) -> Result<Vec<MigrationRecord>, ServiceError>;
+    /// Runs `migration.up` and records its bookkeeping row in a single transaction.
+    fn apply_migration(&self, migration: &Migration) -> Result<(), ServiceError>;
+    /// Runs `migration.down` and deletes its bookkeeping row in a single transaction.
+    fn revert_migration(&self, migration: &Migration) -> Result<(), ServiceError>;
+}
+
+/// Verifies applied checksums still match, then runs every pending migration in order.
+pub fn migrate(
+    executor: &dyn MigrationExecutor,
+    migrations: &[Migration],
+    target_version: i64,
+) -> Result<(), ServiceError> {
+    executor.ensure_schema_migrations_table()?;
+    let applied = executor.applied_migrations()?;
+
+    for record in applied.iter() {
+        match migrations.iter().find(|m| m.version == record.version) {
+            Some(migration) if migration.checksum == record.checksum => {}
+            _ => return Err(ServiceError::CheckFailed),
+        }
+    }
+
+    let applied_versions: std::collections::HashSet<i64> =
+        applied.iter().map(|r| r.version).collect();
+
+    let mut pending: Vec<&Migration> = migrations
+        .iter()
+        .filter(|m| !applied_versions.contains(&m.version) && m.version <= target_version)
+        .collect();
+    pending.sort_by_key(|m| m.version);
+
+    for migration in pending {
+        executor.apply_migration(migration)?;
+    }
+
+    Ok(())
+}
+
+/// Runs the matching down-migration for every version above `target_version`.
+pub fn rollback(
+    executor: &dyn MigrationExecutor,
+    migrations: &[Migration],
+    target_version: i64,
+) -> Result<(), ServiceError> {
+    let applied = executor.applied_migrations()?;
+
+    let mut to_revert: Vec<&Migration> = Vec::new();
+    for record in applied.iter().filter(|r| r.version > target_version) {
+        match migrations.iter().find(|m| m.version == record.version) {
+            Some(migration) => to_revert.push(migration),
+            None => return Err(ServiceError::CheckFailed),
+        }
+    }
+    to_revert.sort_by_key(|m| std::cmp::Reverse(m.version));
+
+    for migration in to_revert {
+        executor.revert_migration(migration)?;
+    }
+
+    Ok(())
+}
+
+/// Where dumps taken via `Backup` physically live.
+pub trait BackupStorage {
+    fn push(&self, service_id: &str, snapshot_id: &str, reader: &mut dyn Read) -> Result<(), ServiceError>;
+    fn pull(&self, service_id: &str, snapshot_id: &str, writer: &mut dyn Write) -> Result<(), ServiceError>;
+    fn list(&self, service_id: &str) -> Result<Vec<SnapshotDescriptor>, ServiceError>;
+    fn prune(&self, service_id: &str, keep_last: usize) -> Result<(), ServiceError>;
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct SnapshotDescriptor {
+    pub snapshot_id: String,
+    pub key: String,
+    pub size_in_bytes: u64,
+    pub created_at: String,
+}
+
+pub struct S3Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// An S3-compatible `BackupStorage`, driven through the `aws` CLI so both AWS and
+/// MinIO-style endpoints work behind the same `--endpoint-url`.
+pub struct S3BackupStorage {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub prefix: String,
+    pub credentials: S3Credentials,
+}
+
+impl S3BackupStorage {
+    pub fn new(
+        endpoint: String,
+        region: String,
+        bucket: String,
+        prefix: String,
+        credentials: S3Credentials,
+    ) -> Self {
+        S3BackupStorage {
+            endpoint,
+            region,
+            bucket,
+            prefix,
+            credentials,
+        }
+    }
+
+    fn object_key(&self, service_id: &str, snapshot_id: &str) -> String {
+        format!("{}/{}/{}.dump", self.prefix, service_id, snapshot_id)
+    }
+
+    /// Scopes credentials to this invocation via env vars rather than relying on
+    /// whatever ambient AWS identity the engine process happens to have, so a
+    /// MinIO destination's static credentials never collide with AWS ones.
+    fn aws(&self) -> Command {
+        let mut command = Command::new("aws");
+        command
+            .env("AWS_ACCESS_KEY_ID", self.credentials.access_key_id.as_str())
+            .env("AWS_SECRET_ACCESS_KEY", self.credentials.secret_access_key.as_str())
+            .args(["--endpoint-url", self.endpoint.as_str(), "--region", self.region.as_str()]);
+        command
+    }
+}
+
+impl BackupStorage for S3BackupStorage {
+    fn push(&self, service_id: &str, snapshot_id: &str, reader: &mut dyn Read) -> Result<(), ServiceError> {
+        let mut child = self
+            .aws()
+            .args(["s3", "cp", "-", format!("s3://{}/{}", self.bucket, self.object_key(service_id, snapshot_id)).as_str()])
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+        {
+            let mut stdin = child
+                .stdin
+                .take()
+                .ok_or_else(|| ServiceError::Unexpected("no stdin on aws s3 cp".to_string()))?;
+            // Stream directly into the child's stdin instead of buffering the whole
+            // dump in memory; `stdin` is dropped at the end of this block, closing
+            // the pipe so `aws s3 cp` sees EOF.
+            std::io::copy(reader, &mut stdin).map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+        }
+
+        let status = child.wait().map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+        if !status.success() {
+            return Err(ServiceError::Unexpected(format!(
+                "aws s3 cp exited with {}",
+                status
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn pull(&self, service_id: &str, snapshot_id: &str, writer: &mut dyn Write) -> Result<(), ServiceError> {
+        let output = self
+            .aws()
+            .args(["s3", "cp", format!("s3://{}/{}", self.bucket, self.object_key(service_id, snapshot_id)).as_str(), "-"])
+            .output()
+            .map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+        if !output.status.success() {
+            return Err(ServiceError::Unexpected(format!(
+                "aws s3 cp exited with {}",
+                output.status
+            )));
+        }
+
+        writer
+            .write_all(&output.stdout)
+            .map_err(|e| ServiceError::Unexpected(e.to_string()))
+    }
+
+    fn list(&self, service_id: &str) -> Result<Vec<SnapshotDescriptor>, ServiceError> {
+        let mut snapshots = Vec::new();
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let prefix = format!("{}/{}/", self.prefix, service_id);
+            let mut args = vec!["s3api", "list-objects-v2", "--bucket", self.bucket.as_str(), "--prefix", prefix.as_str()];
+            if let Some(token) = continuation_token.as_deref() {
+                args.push("--starting-token");
+                args.push(token);
+            }
+
+            let output = self
+                .aws()
+                .args(args)
+                .output()
+                .map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+            if !output.status.success() {
+                return Err(ServiceError::Unexpected(format!(
+                    "aws s3api list-objects-v2 exited with {}",
+                    output.status
+                )));
+            }
+
+            let (page, next_token) = parse_snapshot_catalog(&output.stdout)?;
+            snapshots.extend(page);
+
+            match next_token {
+                Some(token) => continuation_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    fn prune(&self, service_id: &str, keep_last: usize) -> Result<(), ServiceError> {
+        let mut snapshots = self.list(service_id)?;
+        snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        for snapshot in snapshots.into_iter().skip(keep_last) {
+            let status = self
+                .aws()
+                .args([
+                    "s3",
+                    "rm",
+                    format!("s3://{}/{}", self.bucket, snapshot.key).as_str(),
+                ])
+                .status()
+                .map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+            if !status.success() {
+                return Err(ServiceError::Unexpected(format!(
+                    "aws s3 rm exited with {}",
+                    status
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses one page of `aws s3api list-objects-v2` output, returning the `Contents`
+/// entries as `SnapshotDescriptor`s plus the `NextContinuationToken` if the listing
+/// was truncated, so `S3BackupStorage::list` can page through it.
+fn parse_snapshot_catalog(raw_json: &[u8]) -> Result<(Vec<SnapshotDescriptor>, Option<String>), ServiceError> {
+    let root: JsonValue = serde_json::from_slice(raw_json).map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+    let mut snapshots = Vec::new();
+    for object in root.get("Contents").and_then(JsonValue::as_array).into_iter().flatten() {
+        let key = match object.get("Key").and_then(JsonValue::as_str) {
+            Some(key) => key.to_string(),
+            None => continue,
+        };
+        let size_in_bytes = object.get("Size").and_then(JsonValue::as_u64).unwrap_or(0);
+        let created_at = object
+            .get("LastModified")
+            .and_then(JsonValue::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let snapshot_id = key
+            .rsplit('/')
+            .next()
+            .unwrap_or(key.as_str())
+            .trim_end_matches(".dump")
+            .to_string();
+
+        snapshots.push(SnapshotDescriptor {
+            snapshot_id,
+            key,
+            size_in_bytes,
+            created_at,
+        });
+    }
+
+    let next_token = root
+        .get("NextContinuationToken")
+        .and_then(JsonValue::as_str)
+        .map(str::to_string);
+
+    Ok((snapshots, next_token))
+}
+
+/// Writes a logical dump of `database_type`, chosen from the `DatabaseType`, to a
+/// temporary file under `/tmp`, then streams that file into `storage` under
+/// `{storage.prefix}/{service_id}/{snapshot_id}.dump`; the dump is not piped into
+/// `storage` directly. The temporary file is removed only after a successful push. Callers scope
+/// backups to an organization/environment by setting `DatabaseOptions::backup_prefix`
+/// (and thus `storage`'s `prefix`) to `{organization_id}/{environment_id}`.
+pub fn backup_database(
+    storage: &dyn BackupStorage,
+    database_type: &DatabaseType,
+    service_id: &str,
+    snapshot_id: &str,
+) -> Result<(), ServiceError> {
+    let dump_path = format!("/tmp/{}-{}.dump", service_id, snapshot_id);
+
+    let status = database_type
+        .dump_command(dump_path.as_str())
+        .status()
+        .map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+    if !status.success() {
+        return Err(ServiceError::Unexpected(format!(
+            "dump command exited with {}",
+            status
+        )));
+    }
+
+    let mut dump_file =
+        std::fs::File::open(&dump_path).map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+    storage.push(service_id, snapshot_id, &mut dump_file)?;
+
+    let _ = std::fs::remove_file(&dump_path);
+
+    Ok(())
+}
+
+/// Locates the latest (or a named) snapshot via `storage.list`/`pull` and restores it.
+pub fn restore_database(
+    storage: &dyn BackupStorage,
+    database_type: &DatabaseType,
+    service_id: &str,
+    snapshot_id: Option<&str>,
+) -> Result<(), ServiceError> {
+    let snapshot_id = match snapshot_id {
+        Some(id) => id.to_string(),
+        None => {
+            let mut snapshots = storage.list(service_id)?;
+            snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            snapshots
+                .into_iter()
+                .next()
+                .map(|s| s.snapshot_id)
+                .ok_or_else(|| ServiceError::Unexpected(format!("no snapshot found for {}", service_id)))?
+        }
+    };
+
+    let restore_path = format!("/tmp/{}-{}.restore", service_id, snapshot_id);
+    let mut restore_file = std::fs::File::create(&restore_path)
+        .map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+    storage.pull(service_id, snapshot_id.as_str(), &mut restore_file)?;
+
+    let status = database_type
+        .restore_command(restore_path.as_str())
+        .status()
+        .map_err(|e| ServiceError::Unexpected(e.to_string()))?;
+
+    let _ = std::fs::remove_file(&restore_path);
+
+    if !status.success() {
+        return Err(ServiceError::Unexpected(format!(
+            "restore command exited with {}",
+            status
+        )));
+    }
+
+    Ok(())
+}
+
+/// Inserts the snapshot catalog so templated disaster-recovery manifests can reference it.
+pub fn backup_tera_context(snapshots: &[SnapshotDescriptor], context: &mut TeraContext) {
+    let keys: Vec<&str> = snapshots.iter().map(|s| s.key.as_str()).collect();
+    context.insert("backup_snapshot_keys", &keys);
+    context.insert(
+        "latest_backup_snapshot_key",
+        &snapshots.iter().max_by(|a, b| a.created_at.cmp(&b.created_at)).map(|s| s.key.as_str()),
+    );
+}
+
+/// What a single readiness attempt should check.
+#[derive(Clone, Eq, PartialEq)]
+pub enum ProbeSpec {
+    Tcp { port: u16 },
+    Http { port: u16, path: String, expect_status: u16 },
+    Exec { command: String },
+}
+
+/// Bounds on how `wait_until_ready` retries a `ProbeSpec`. Backoff starts at
+/// `backoff_interval` and is multiplied by `backoff_multiplier` (2 by default) after
+/// each failed attempt, capped at `max_backoff`.
+#[derive(Clone, Copy)]
+pub struct ProbePolicy {
+    pub attempt_timeout: Duration,
+    pub total_deadline: Duration,
+    pub backoff_interval: Duration,
+    pub backoff_multiplier: u32,
+    pub max_backoff: Duration,
+}
+
+impl Default for ProbePolicy {
+    fn default() -> Self {
+        ProbePolicy {
+            attempt_timeout: Duration::from_secs(5),
+            total_deadline: Duration::from_secs(120),
+            backoff_interval: Duration::from_secs(2),
+            backoff_multiplier: 2,
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// How many attempts `wait_until_ready` made before giving up, and for how long.
+#[derive(Clone, Debug)]
+pub struct ReadinessReport {
+    pub attempts: u32,
+    pub elapsed: Duration,
+}
+
+/// Polls `spec` against `ip` until it succeeds or `policy`'s total deadline elapses.
+/// Shared by `Service::wait_until_ready` and callers probing an endpoint other than
+/// the service's own `probe_spec()` (e.g. an operator-managed pooler).
+pub fn wait_until_ready_for(spec: &ProbeSpec, ip: &str, policy: ProbePolicy) -> Result<(), ServiceError> {
+    let start = Instant::now();
+    let mut attempts = 0u32;
+    let mut backoff = policy.backoff_interval;
+
+    loop {
+        attempts += 1;
+
+        let remaining = policy.total_deadline.saturating_sub(start.elapsed());
+        if run_probe_attempt(spec, ip, policy.attempt_timeout.min(remaining)) {
+            return Ok(());
+        }
+
+        if start.elapsed() >= policy.total_deadline {
+            return Err(ServiceError::ProbeFailed(ReadinessReport {
+                attempts,
+                elapsed: start.elapsed(),
+            }));
+        }
+
+        std::thread::sleep(backoff.min(policy.total_deadline.saturating_sub(start.elapsed())));
+        backoff =(backoff * policy.backoff_multiplier).min(policy.max_backoff);
+    }
+}
+
+/// Runs a single probe attempt on a bounded thread so a hung connect/HTTP call can
+/// never stall the caller past `attempt_timeout`.
+fn run_probe_attempt(spec: &ProbeSpec, ip: &str, attempt_timeout: Duration) -> bool {
+    let spec = spec.clone();
+    let ip = ip.to_string();
+    let (sender, receiver) = mpsc::channel();
+
+    let _ = std::thread::spawn(move || {
+        let result = match &spec {
+            ProbeSpec::Tcp { port } => connect_timeout(ip.as_str(), *port, attempt_timeout).is_ok(),
+            ProbeSpec::Http {
+                port,
+                path,
+                expect_status,
+            } => probe_http(ip.as_str(), *port, path.as_str(), *expect_status, attempt_timeout),
+            ProbeSpec::Exec { command } => Command::new("sh")
+                .args(["-c", command.as_str()])
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false),
+        };
+
+        // The receiver may already be gone if the attempt timed out; that's fine.
+        let _ = sender.send(result);
+    });
+
+    receiver.recv_timeout(attempt_timeout).unwrap_or(false)
+}
+
+/// Resolves `(host, port)` through the standard DNS-aware `ToSocketAddrs` machinery
+/// (so hostnames work, not just IP literals) and connects with a hard timeout.
+fn connect_timeout(host: &str, port: u16, timeout: Duration) -> std::io::Result<TcpStream> {
+    let addr = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no addresses resolved"))?;
+
+    TcpStream::connect_timeout(&addr, timeout)
+}
+
+fn probe_http(ip: &str, port: u16, path: &str, expect_status: u16, timeout: Duration) -> bool {
+    let mut stream = match connect_timeout(ip, port, timeout) {
+        Ok(stream) => stream,
+        Err(_) => return false,
+    };
+    let _ = stream.set_read_timeout(Some(timeout));
+
+    let request = format!("GET {} HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n", path, ip);
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+
+    let mut response = String::new();
+    if stream.read_to_string(&mut response).is_err() {
+        return false;
+    }
+
+    response
+        .lines()
+        .next()
+        .map(|status_line| status_line.contains(&expect_status.to_string()))
+        .unwrap_or(false)
+}
+
+/// The `/metrics` surface a service exposes, scraped by its consolidated sidecar.
+#[derive(Clone, Eq, PartialEq)]
+pub struct MetricsEndpoint {
+    pub port: u16,
+    pub path: String,
+}
+
+/// A service whose runtime metrics can be scraped through a consolidated sidecar,
+/// rather than deploying a dedicated exporter per workload.
+pub trait Monitorable {
+    fn metrics_endpoint(&self) -> Option<MetricsEndpoint>;
+
+    /// Only `Database` services get a wrapped-exporter sidecar; `Application`/`Router`/
+    /// `ExternalService` already expose HTTP request metrics on their own `metrics_endpoint`
+    /// and are scraped directly, with no extra container needed.
+    fn metrics_sidecar(&self, service_type: &ServiceType) -> Option<MetricsSidecar> {
+        match service_type {
+            ServiceType::Database(database_type) => self
+                .metrics_endpoint()
+                .map(|endpoint| MetricsSidecar::for_database(database_type, &endpoint)),
+            ServiceType::Application | ServiceType::Router | ServiceType::ExternalService => None,
+        }
+    }
+
+    /// Inserts the metrics-scraping keys, gated behind `enabled` so a per-environment
+    /// toggle controls whether metrics injection happens at all. `Database` services get
+    /// a sidecar (image/port/scrape path); other service types are scraped directly on
+    /// their own `metrics_endpoint`.
+    fn metrics_tera_context(
+        &self,
+        service_type: &ServiceType,
+        service_id: &str,
+        environment_id: &str,
+        enabled: bool,
+        context: &mut TeraContext,
+    ) {
+        context.insert("metrics_sidecar_enabled", &enabled);
+
+        if !enabled {
+            return;
+        }
+
+        let labels = vec![
+            ("service_id".to_string(), service_id.to_string()),
+            ("service_type".to_string(), service_type.name().to_string()),
+            ("environment_id".to_string(), environment_id.to_string()),
+        ];
+
+        match self.metrics_sidecar(service_type) {
+            Some(sidecar) => {
+                context.insert("metrics_sidecar_image", &sidecar.image);
+                context.insert("metrics_sidecar_port", &sidecar.port);
+                context.insert("metrics_sidecar_scrape_path", &sidecar.scrape_path);
+            }
+            None => {
+                if let Some(endpoint) = self.metrics_endpoint() {
+                    context.insert("metrics_scrape_port", &endpoint.port);
+                    context.insert("metrics_scrape_path", &endpoint.path);
+                }
+            }
+        }
+
+        context.insert("metrics_labels", &labels);
+    }
+}
+
+/// The engine-specific exporter, wrapped as a sidecar co-located with a `Database`.
+#[derive(Clone)]
+pub struct MetricsSidecar {
+    pub image: String,
+    pub port: u16,
+    pub scrape_path: String,
+}
+
+impl MetricsSidecar {
+    fn for_database(database_type: &DatabaseType, endpoint: &MetricsEndpoint) -> MetricsSidecar {
+        MetricsSidecar {
+            image: exporter_image(database_type).to_string(),
+            port: endpoint.port,
+            scrape_path: endpoint.path.clone(),
+        }
+    }
+}
+
+fn exporter_image(database_type: &DatabaseType) -> &'static str {
+    match database_type {
+        DatabaseType::PostgreSQL(_) => "quay.io/prometheuscommunity/postgres-exporter",
+        DatabaseType::MySQL(_) => "prom/mysqld-exporter",
+        DatabaseType::MongoDB(_) => "percona/mongodb_exporter",
+    }
+}
+
+/// A single Prometheus relabeling rule.
+#[derive(Clone)]
+pub struct RelabelRule {
+    pub source_labels: Vec<String>,
+    pub target_label: String,
+}
+
+/// Relabeling rules letting a cluster-level collector discover every injected sidecar
+/// by its `service_id`/`service_type`/`environment_id` labels.
+pub fn scrape_config() -> Vec<RelabelRule> {
+    vec![
+        RelabelRule {
+            source_labels: vec!["__meta_kubernetes_pod_label_service_id".to_string()],
+            target_label: "service_id".to_string(),
+        },
+        RelabelRule {
+            source_labels: vec!["__meta_kubernetes_pod_label_service_type".to_string()],
+            target_label: "service_type".to_string(),
+        },
+        RelabelRule {
+            source_labels: vec!["__meta_kubernetes_pod_label_environment_id".to_string()],
+            target_label: "environment_id".to_string(),
+        },
+    ]
+}
+
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub enum Action {
     Create,
@@ -190,6 +941,54 @@ pub struct DatabaseOptions {
     pub port: u16,
     pub disk_size_in_gib: u32,
     pub database_disk_type: String,
+    pub backup_bucket: String,
+    pub backup_prefix: String,
+}
+
+impl DatabaseOptions {
+    /// Builds the `S3BackupStorage` that `backup_database`/`restore_database` should
+    /// push/pull through for this database, scoped to its `backup_bucket`/`backup_prefix`.
+    /// `endpoint`/`region`/`credentials` come from the cloud provider's S3-compatible
+    /// destination, which is shared across databases rather than per-database.
+    pub fn backup_storage(
+        &self,
+        endpoint: String,
+        region: String,
+        credentials: S3Credentials,
+    ) -> S3BackupStorage {
+        S3BackupStorage::new(
+            endpoint,
+            region,
+            self.backup_bucket.clone(),
+            self.backup_prefix.clone(),
+            credentials,
+        )
+    }
+}
+
+/// Whether a `Database` is a single raw instance or handed off to a Kubernetes
+/// operator that manages primary/replica topology and automated failover.
+#[derive(Clone, Eq, PartialEq)]
+pub enum DeploymentStrategy {
+    Raw,
+    Operator,
+}
+
+/// The connection pooler bundled alongside an operator-managed database cluster.
+#[derive(Clone, Eq, PartialEq)]
+pub struct PoolerSpec {
+    pub image: String,
+    pub port: u16,
+    pub max_connections: u32,
+}
+
+/// Primary/replica shape of an operator-managed database cluster.
+#[derive(Clone, Eq, PartialEq)]
+pub struct DatabaseTopology {
+    pub instances: u16,
+    pub storage_class: String,
+    pub synchronous_replicas: u16,
+    pub pooler: Option<PoolerSpec>,
 }
 
 #[derive(Eq, PartialEq)]
@@ -199,6 +998,143 @@ pub enum DatabaseType<'a> {
     MySQL(&'a DatabaseOptions),
 }
 
+impl<'a> DatabaseType<'a> {
+    /// Whether this engine is provisioned as a single raw instance or handed off to a
+    /// Kubernetes operator that manages topology, failover and the connection pooler.
+    pub fn deployment_strategy(&self) -> DeploymentStrategy {
+        match self {
+            DatabaseType::PostgreSQL(_) => DeploymentStrategy::Operator,
+            DatabaseType::MySQL(_) => DeploymentStrategy::Operator,
+            DatabaseType::MongoDB(_) => DeploymentStrategy::Raw,
+        }
+    }
+
+    fn operator_name(&self) -> &'static str {
+        match self {
+            DatabaseType::PostgreSQL(_) => "cloudnative-pg",
+            DatabaseType::MySQL(_) => "percona-xtradb-cluster-operator",
+            DatabaseType::MongoDB(_) => "percona-server-mongodb-operator",
+        }
+    }
+
+    pub fn options(&self) -> &DatabaseOptions {
+        match self {
+            DatabaseType::PostgreSQL(options) => options,
+            DatabaseType::MongoDB(options) => options,
+            DatabaseType::MySQL(options) => options,
+        }
+    }
+
+    /// The logical dump binary used to produce a portable snapshot of this engine.
+    fn dump_command(&self, destination_path: &str) -> Command {
+        let options = self.options();
+
+        let mut command = match self {
+            DatabaseType::PostgreSQL(_) => {
+                let mut c = Command::new("pg_dump");
+                c.arg(format!(
+                    "postgresql://{}:{}@{}:{}",
+                    options.login, options.password, options.host, options.port
+                ));
+                c.args(["--format", "custom", "--file", destination_path]);
+                c
+            }
+            DatabaseType::MySQL(_) => {
+                let mut c = Command::new("mysqldump");
+                c.args([
+                    "-h",
+                    options.host.as_str(),
+                    "-P",
+                    options.port.to_string().as_str(),
+                    "-u",
+                    options.login.as_str(),
+                    format!("-p{}", options.password).as_str(),
+                    "--result-file",
+                    destination_path,
+                ]);
+                c
+            }
+            DatabaseType::MongoDB(_) => {
+                let mut c = Command::new("mongodump");
+                c.args([
+                    "--host",
+                    options.host.as_str(),
+                    "--port",
+                    options.port.to_string().as_str(),
+                    "--username",
+                    options.login.as_str(),
+                    "--password",
+                    options.password.as_str(),
+                    "--archive",
+                    destination_path,
+                ]);
+                c
+            }
+        };
+
+        command.stdout(Stdio::null()).stderr(Stdio::piped());
+        command
+    }
+
+    /// The restore binary counterpart to `dump_command`.
+    fn restore_command(&self, source_path: &str) -> Command {
+        let options = self.options();
+
+        let mut command = match self {
+            DatabaseType::PostgreSQL(_) => {
+                let mut c = Command::new("pg_restore");
+                c.args([
+                    "--clean",
+                    "--if-exists",
+                    "--dbname",
+                    format!(
+                        "postgresql://{}:{}@{}:{}",
+                        options.login, options.password, options.host, options.port
+                    )
+                    .as_str(),
+                    source_path,
+                ]);
+                c
+            }
+            DatabaseType::MySQL(_) => {
+                let mut c = Command::new("mysql");
+                c.args([
+                    "-h",
+                    options.host.as_str(),
+                    "-P",
+                    options.port.to_string().as_str(),
+                    "-u",
+                    options.login.as_str(),
+                    format!("-p{}", options.password).as_str(),
+                    "-e",
+                    format!("source {}", source_path).as_str(),
+                ]);
+                c
+            }
+            DatabaseType::MongoDB(_) => {
+                let mut c = Command::new("mongorestore");
+                c.args([
+                    "--host",
+                    options.host.as_str(),
+                    "--port",
+                    options.port.to_string().as_str(),
+                    "--username",
+                    options.login.as_str(),
+                    "--password",
+                    options.password.as_str(),
+                    "--archive",
+                    source_path,
+                    "--drop",
+                ]);
+                c
+            }
+        };
+
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        command
+    }
+}
+
 #[derive(Eq, PartialEq)]
 pub enum ServiceType<'a> {
     Application,
@@ -222,6 +1158,7 @@ impl<'a> ServiceType<'a> {
 pub enum ServiceError {
     OnCreateFailed,
     CheckFailed,
+    ProbeFailed(ReadinessReport),
     Cmd(CmdError),
     Io(Error),
     NotEnoughResources(String),
@@ -253,3 +1190,135 @@ impl From<CommitError> for Option<ServiceError> {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// An in-memory `MigrationExecutor` so `migrate`/`rollback` can be exercised
+    /// without a real database.
+    #[derive(Default)]
+    struct FakeExecutor {
+        applied: RefCell<Vec<MigrationRecord>>,
+        reverted: RefCell<Vec<i64>>,
+    }
+
+    impl MigrationExecutor for FakeExecutor {
+        fn ensure_schema_migrations_table(&self) -> Result<(), ServiceError> {
+            Ok(())
+        }
+
+        fn applied_migrations(&self) -> Result<Vec<MigrationRecord>, ServiceError> {
+            Ok(self.applied.borrow().clone())
+        }
+
+        fn apply_migration(&self, migration: &Migration) -> Result<(), ServiceError> {
+            self.applied.borrow_mut().push(MigrationRecord {
+                version: migration.version,
+                checksum: migration.checksum.clone(),
+                applied_at: "now".to_string(),
+            });
+            Ok(())
+        }
+
+        fn revert_migration(&self, migration: &Migration) -> Result<(), ServiceError> {
+            self.applied.borrow_mut().retain(|r| r.version != migration.version);
+            self.reverted.borrow_mut().push(migration.version);
+            Ok(())
+        }
+    }
+
+    fn migration(version: i64, checksum: &str) -> Migration {
+        Migration {
+            version,
+            checksum: checksum.to_string(),
+            up: format!("up {}", version),
+            down: format!("down {}", version),
+        }
+    }
+
+    #[test]
+    fn migrate_applies_pending_migrations_up_to_target_version() {
+        let executor = FakeExecutor::default();
+        let migrations = vec![migration(1, "a"), migration(2, "b"), migration(3, "c")];
+
+        migrate(&executor, &migrations, 2).unwrap();
+
+        let applied = executor.applied_migrations().unwrap();
+        assert_eq!(applied.iter().map(|r| r.version).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn migrate_fails_when_applied_checksum_has_drifted() {
+        let executor = FakeExecutor::default();
+        executor.applied.borrow_mut().push(MigrationRecord {
+            version: 1,
+            checksum: "stale".to_string(),
+            applied_at: "now".to_string(),
+        });
+        let migrations = vec![migration(1, "current")];
+
+        let result = migrate(&executor, &migrations, 1);
+
+        assert!(matches!(result, Err(ServiceError::CheckFailed)));
+    }
+
+    #[test]
+    fn rollback_reverts_migrations_above_target_version_in_reverse_order() {
+        let executor = FakeExecutor::default();
+        let migrations = vec![migration(1, "a"), migration(2, "b"), migration(3, "c")];
+        migrate(&executor, &migrations, 3).unwrap();
+
+        rollback(&executor, &migrations, 1).unwrap();
+
+        let applied = executor.applied_migrations().unwrap();
+        assert_eq!(applied.iter().map(|r| r.version).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(*executor.reverted.borrow(), vec![3, 2]);
+    }
+
+    #[test]
+    fn rollback_fails_when_an_applied_version_has_no_matching_migration() {
+        let executor = FakeExecutor::default();
+        executor.applied.borrow_mut().push(MigrationRecord {
+            version: 2,
+            checksum: "b".to_string(),
+            applied_at: "now".to_string(),
+        });
+        let migrations = vec![migration(1, "a")];
+
+        let result = rollback(&executor, &migrations, 0);
+
+        assert!(matches!(result, Err(ServiceError::CheckFailed)));
+    }
+
+    #[test]
+    fn parse_snapshot_catalog_extracts_entries_and_continuation_token() {
+        let raw = r#"{
+            "Contents": [
+                {"Key": "backups/db/2024-01-01T00:00:00Z.dump", "Size": 1024, "LastModified": "2024-01-01T00:00:00Z"},
+                {"Key": "backups/db/with \"quotes\" and é.dump", "Size": 2048, "LastModified": "2024-01-02T00:00:00Z"}
+            ],
+            "IsTruncated": true,
+            "NextContinuationToken": "token-123"
+        }"#;
+
+        let (snapshots, next_token) = parse_snapshot_catalog(raw.as_bytes()).unwrap();
+
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].snapshot_id, "2024-01-01T00:00:00Z");
+        assert_eq!(snapshots[0].size_in_bytes, 1024);
+        assert_eq!(snapshots[1].key, "backups/db/with \"quotes\" and \u{e9}.dump");
+        assert_eq!(next_token, Some("token-123".to_string()));
+    }
+
+    #[test]
+    fn parse_snapshot_catalog_returns_no_continuation_token_on_last_page() {
+        let raw = br#"{"Contents": [], "IsTruncated": false}"#;
+
+        let (snapshots, next_token) = parse_snapshot_catalog(raw).unwrap();
+
+        assert!(snapshots.is_empty());
+        assert_eq!(next_token, None);
+    }
+}